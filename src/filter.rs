@@ -0,0 +1,131 @@
+use globset::GlobMatcher;
+
+use crate::pathiterator::{FileIterator, IteratorItem};
+
+struct Node {
+    item: IteratorItem,
+    children: Vec<Node>,
+}
+
+/// Wraps a `FileIterator` and drops files that don't match `--include`,
+/// pruning directories that end up with no surviving descendants as a
+/// result. `--exclude`/`--gitignore` are applied earlier, in
+/// `pathiterator`, before directory sizes are summed — by the time entries
+/// reach here they're already gone, not just hidden.
+pub struct FilteredIterator {
+    inner: FileIterator,
+    include_glob: Option<GlobMatcher>,
+    skip: bool,
+    buffer: Option<std::vec::IntoIter<IteratorItem>>,
+}
+
+impl FilteredIterator {
+    pub fn new(inner: FileIterator) -> FilteredIterator {
+        let include_glob = inner.include_glob().cloned();
+
+        FilteredIterator {
+            inner,
+            include_glob,
+            skip: false,
+            buffer: None,
+        }
+    }
+
+    /// Bypasses filtering entirely, so entries stream straight through
+    /// without being buffered. Use when no `--include` pattern is active.
+    pub fn skip_filter(&mut self) {
+        self.skip = true;
+    }
+
+    fn build_tree(items: &mut std::vec::IntoIter<IteratorItem>, level: usize) -> Vec<Node> {
+        let mut nodes = Vec::new();
+
+        while let Some(item) = items.as_slice().first() {
+            if item.level < level {
+                break;
+            }
+
+            let item = items.next().expect("peeked item must exist");
+            let children = Self::build_tree(items, level + 1);
+            nodes.push(Node { item, children });
+        }
+
+        nodes
+    }
+
+    /// A directory is kept only if at least one descendant matches
+    /// `--include` or is a synthetic aggregated entry (those are always
+    /// kept); an empty directory has none, so it's dropped like any
+    /// other non-matching entry. `--exclude`/`--gitignore` membership is
+    /// decided earlier, in `pathiterator`, before a directory's children are
+    /// even read — a directory that's empty there because everything under
+    /// it is excluded never reaches this tree with phantom children to miss,
+    /// so there's no "originally empty" case left for this function to
+    /// special-case.
+    fn keep(node: &Node, include_glob: Option<&GlobMatcher>) -> bool {
+        if node.item.aggregated.is_some() {
+            return true;
+        }
+
+        if node.item.is_dir() {
+            node.children
+                .iter()
+                .any(|child| Self::keep(child, include_glob))
+        } else {
+            include_glob.map_or(true, |glob| glob.is_match(&node.item.file_name))
+        }
+    }
+
+    /// Flattens the surviving nodes into `out`. Siblings that fail `keep`
+    /// are dropped and shrink the displayed set, so `is_last` is recomputed against the
+    /// *surviving* siblings here rather than trusting whatever the
+    /// pre-filter tree set it to — otherwise the new last visible entry
+    /// would still be drawn as a mid-list branch.
+    fn flatten(
+        mut nodes: Vec<Node>,
+        include_glob: Option<&GlobMatcher>,
+        is_root: bool,
+        out: &mut Vec<IteratorItem>,
+    ) {
+        if !is_root {
+            nodes.retain(|node| Self::keep(node, include_glob));
+        }
+
+        let last_index = nodes.len().checked_sub(1);
+
+        for (index, mut node) in nodes.into_iter().enumerate() {
+            node.item.is_last = Some(index) == last_index;
+
+            let is_dir = node.item.is_dir();
+            let children = node.children;
+
+            out.push(node.item);
+
+            if is_dir {
+                Self::flatten(children, include_glob, false, out);
+            }
+        }
+    }
+}
+
+impl Iterator for FilteredIterator {
+    type Item = IteratorItem;
+
+    fn next(&mut self) -> Option<IteratorItem> {
+        if self.skip {
+            return self.inner.next();
+        }
+
+        if self.buffer.is_none() {
+            let mut items = self.inner.by_ref().collect::<Vec<_>>().into_iter();
+            let tree = Self::build_tree(&mut items, 0);
+
+            let mut flattened = Vec::new();
+            Self::flatten(tree, self.include_glob.as_ref(), true, &mut flattened);
+
+            self.buffer = Some(flattened.into_iter());
+        }
+
+        self.buffer.as_mut().and_then(Iterator::next)
+    }
+}
@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobMatcher};
+
+/// A single parsed `.gitignore` line.
+#[derive(Clone)]
+struct Rule {
+    matcher: GlobMatcher,
+    negate: bool,
+    dir_only: bool,
+}
+
+/// The `.gitignore` rules accumulated while descending into a directory
+/// tree, each layer anchored to the directory that defined it.
+#[derive(Clone, Default)]
+pub struct GitignoreStack {
+    layers: Vec<(PathBuf, Vec<Rule>)>,
+}
+
+impl GitignoreStack {
+
+    /// Returns a stack extended with the `.gitignore` found directly in
+    /// `dir`, if any; rules in it apply to `dir` and everything below it.
+    pub fn extended(&self, dir: &Path) -> GitignoreStack {
+        let rules = parse(&dir.join(".gitignore"));
+
+        if rules.is_empty() {
+            return self.clone();
+        }
+
+        let mut layers = self.layers.clone();
+        layers.push((dir.to_path_buf(), rules));
+        GitignoreStack { layers }
+    }
+
+    /// Whether `path` should be excluded, applying every accumulated layer
+    /// in order so a more specific (deeper) `.gitignore` can override a
+    /// shallower one, and a later line in a file can negate an earlier one.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let mut ignored = false;
+
+        for (base, rules) in &self.layers {
+            let Ok(relative) = path.strip_prefix(base) else {
+                continue;
+            };
+
+            for rule in rules {
+                if rule.dir_only && !is_dir {
+                    continue;
+                }
+
+                if rule.matcher.is_match(relative) {
+                    ignored = !rule.negate;
+                }
+            }
+        }
+
+        ignored
+    }
+}
+
+fn parse(path: &Path) -> Vec<Rule> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    contents.lines().filter_map(parse_line).collect()
+}
+
+fn parse_line(line: &str) -> Option<Rule> {
+    let line = line.trim_end();
+
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let negate = line.starts_with('!');
+    let pattern = if negate { &line[1..] } else { line };
+
+    let dir_only = pattern.ends_with('/');
+    let pattern = pattern.trim_end_matches('/');
+
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let anchored = pattern.starts_with('/');
+    let trimmed = pattern.trim_start_matches('/');
+
+    // A pattern with no inner `/` matches at any depth, like a plain glob;
+    // an anchored or nested pattern matches relative to the `.gitignore`.
+    let glob_pattern = if anchored || trimmed.contains('/') {
+        trimmed.to_string()
+    } else {
+        format!("**/{trimmed}")
+    };
+
+    let matcher = Glob::new(&glob_pattern).ok()?.compile_matcher();
+
+    Some(Rule {
+        matcher,
+        negate,
+        dir_only,
+    })
+}
@@ -0,0 +1,45 @@
+use std::fs::Metadata;
+use std::path::Path;
+
+use crate::pathiterator::IteratorItem;
+
+const FOLDER_ICON: &str = "\u{f115}"; //  nf-fa-folder
+const GENERIC_ICON: &str = "\u{f15b}"; //  nf-fa-file
+
+/// Extension -> Nerd Font glyph, grouped loosely by category. Unmatched
+/// extensions fall back to `GENERIC_ICON`.
+const EXTENSION_ICONS: &[(&str, &str)] = &[
+    ("rs", "\u{e7a8}"),   //  Rust
+    ("toml", "\u{e615}"), //  config
+    ("json", "\u{e60b}"), //  config
+    ("yml", "\u{e615}"),
+    ("yaml", "\u{e615}"),
+    ("md", "\u{f48a}"), //  Markdown
+    ("txt", "\u{f0f6}"),
+    ("zip", "\u{f410}"), //  archive
+    ("tar", "\u{f410}"),
+    ("gz", "\u{f410}"),
+    ("png", "\u{f1c5}"), //  image
+    ("jpg", "\u{f1c5}"),
+    ("jpeg", "\u{f1c5}"),
+    ("gif", "\u{f1c5}"),
+    ("svg", "\u{f1c5}"),
+];
+
+/// Resolves the glyph to print before an entry's name: a folder icon for
+/// directories, a per-extension icon for files, or a generic fallback.
+pub fn icon_for(item: &IteratorItem, metadata: &Metadata) -> &'static str {
+    if metadata.is_dir() {
+        return FOLDER_ICON;
+    }
+
+    Path::new(&item.file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(|ext| {
+            EXTENSION_ICONS
+                .iter()
+                .find(|(candidate, _)| candidate.eq_ignore_ascii_case(ext))
+        })
+        .map_or(GENERIC_ICON, |(_, icon)| icon)
+}
@@ -4,26 +4,62 @@
 use clap::Parser;
 
 use std::io;
+use std::io::IsTerminal;
 use std::path::Path;
 use std::{error::Error, fs::Metadata};
 
 use globset::{Glob, GlobMatcher};
-use term::color;
 
 mod filter;
+mod gitignore;
+mod icons;
+mod output;
 mod pathiterator;
+mod size;
+mod theme;
+
+use output::OutputMode;
+use pathiterator::SortKey;
+use theme::Theme;
+
+/// The glyphs used to draw tree branches, swappable via `-A`/`--ascii` for
+/// terminals/locales that mangle the default Unicode box-drawing set.
+struct Charset {
+    horz: char,
+    cross: char,
+    vert: char,
+    last_file: char,
+    /// Filler printed alongside `vert` for a continuing ancestor branch.
+    /// The Unicode default uses a non-breaking space so terminals don't
+    /// trim it; ASCII mode uses a regular space instead.
+    blank: char,
+}
 
-mod dirsign {
-    pub const HORZ: char = '─';
-    pub const CROSS: char = '├';
-    pub const VERT: char = '│';
-    pub const LAST_FILE: char = '└';
-    pub const BLANK: char = '\u{00A0}';
+impl Charset {
+    fn unicode() -> Charset {
+        Charset {
+            horz: '─',
+            cross: '├',
+            vert: '│',
+            last_file: '└',
+            blank: '\u{00A0}',
+        }
+    }
+
+    fn ascii() -> Charset {
+        Charset {
+            horz: '-',
+            cross: '+',
+            vert: '|',
+            last_file: '`',
+            blank: ' ',
+        }
+    }
 }
 
 /// Calculates the indent level in a tree and prints
 /// the correct sign to indicate the hierarchy
-fn set_line_prefix(levels: &[bool], prefix: &mut String) {
+fn set_line_prefix(charset: &Charset, levels: &[bool], prefix: &mut String) {
     let len = levels.len();
     let index = len.saturating_sub(1);
 
@@ -31,9 +67,9 @@ fn set_line_prefix(levels: &[bool], prefix: &mut String) {
 
     levels.iter().take(index).for_each(|level| {
         if *level {
-            prefix.push(dirsign::VERT);
-            prefix.push(dirsign::BLANK);
-            prefix.push(dirsign::BLANK);
+            prefix.push(charset.vert);
+            prefix.push(charset.blank);
+            prefix.push(charset.blank);
         } else {
             prefix.push(' ');
             prefix.push(' ');
@@ -45,48 +81,47 @@ fn set_line_prefix(levels: &[bool], prefix: &mut String) {
 
     if let Some(last) = levels.last() {
         if *last {
-            prefix.push(dirsign::CROSS);
+            prefix.push(charset.cross);
         } else {
-            prefix.push(dirsign::LAST_FILE);
+            prefix.push(charset.last_file);
         }
 
-        prefix.push(dirsign::HORZ);
-        prefix.push(dirsign::HORZ);
+        prefix.push(charset.horz);
+        prefix.push(charset.horz);
         prefix.push(' ');
     }
 }
 
-fn write_color(
-    t: &mut TerminalType,
-    config: &Config,
-    color: color::Color,
-    str: &str,
-) -> io::Result<()> {
+fn write_color(t: &mut TerminalType, config: &Config, sgr: &str, str: &str) -> io::Result<()> {
     if config.use_color {
-        t.fg(color)?;
+        write!(t, "\x1b[{sgr}m")?;
     }
 
     write!(t, "{str}")?;
 
     if config.use_color {
-        t.reset()?;
+        write!(t, "\x1b[0m")?;
     }
 
     Ok(())
 }
 
 fn print_path(
-    file_name: &str,
+    entry: &pathiterator::IteratorItem,
     metadata: &Metadata,
     t: &mut TerminalType,
     config: &Config,
 ) -> io::Result<()> {
-    if metadata.is_dir() {
-        write_color(t, config, color::BRIGHT_BLUE, file_name)
-    } else if is_executable(metadata) {
-        write_color(t, config, color::BRIGHT_GREEN, file_name)
+    let label: std::borrow::Cow<str> = if config.show_icons {
+        let icon = icons::icon_for(entry, metadata);
+        std::borrow::Cow::Owned(format!("{icon} {}", entry.file_name))
     } else {
-        write!(t, "{file_name}")
+        std::borrow::Cow::Borrowed(entry.file_name.as_str())
+    };
+
+    match config.theme.resolve(entry, metadata) {
+        Some(sgr) => write_color(t, config, sgr, &label),
+        None => write!(t, "{label}"),
     }
 }
 
@@ -104,6 +139,30 @@ impl DirEntrySummary {
     }
 }
 
+fn count_entry(summary: &mut DirEntrySummary, entry: &pathiterator::IteratorItem) {
+    if entry.is_dir() {
+        summary.num_folders += 1;
+    } else if let Some(aggregate) = &entry.aggregated {
+        summary.num_files += aggregate.count;
+    } else {
+        summary.num_files += 1;
+    }
+}
+
+/// Tallies a fully collected item list the same way `print_tree` tallies its
+/// streamed entries, for the output modes that need the whole tree up front.
+fn summarize(items: &[pathiterator::IteratorItem]) -> DirEntrySummary {
+    let mut summary = DirEntrySummary::new();
+
+    for entry in items {
+        count_entry(&mut summary, entry);
+    }
+
+    summary.num_folders = summary.num_folders.saturating_sub(1);
+
+    summary
+}
+
 #[cfg(not(target_os = "linux"))]
 fn is_executable(metadata: &Metadata) -> bool {
     false
@@ -116,11 +175,79 @@ fn is_executable(metadata: &Metadata) -> bool {
     (mode & 0o100) != 0
 }
 
+// Each field is an independent rendering/traversal toggle pulled straight
+// from `Args`; grouping them into sub-structs would just relocate the
+// bools without removing any.
+#[allow(clippy::struct_excessive_bools)]
 struct Config {
     use_color: bool,
     show_hidden: bool,
     max_level: usize,
     include_glob: Option<GlobMatcher>,
+    show_size: bool,
+    use_disk_usage: bool,
+    use_bytes: bool,
+    aggregate_threshold: Option<u64>,
+    theme: Theme,
+    threads: usize,
+    sort_key: SortKey,
+    sort_case_sensitive: bool,
+    reverse: bool,
+    dirs_first: bool,
+    exclude_globs: Vec<GlobMatcher>,
+    use_gitignore: bool,
+    show_icons: bool,
+    output_mode: OutputMode,
+    charset: Charset,
+}
+
+/// Controls when `--icons` glyphs are printed.
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum IconsArg {
+    Auto,
+    Always,
+    Never,
+}
+
+/// CLI-facing mirror of `output::OutputMode`.
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum OutputArg {
+    Tree,
+    Json,
+    Paths,
+}
+
+impl From<OutputArg> for OutputMode {
+    fn from(value: OutputArg) -> OutputMode {
+        match value {
+            OutputArg::Tree => OutputMode::Tree,
+            OutputArg::Json => OutputMode::Json,
+            OutputArg::Paths => OutputMode::Paths,
+        }
+    }
+}
+
+/// CLI-facing mirror of `pathiterator::SortKey`; kept separate so the
+/// traversal module doesn't need to know about `clap`.
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum SortArg {
+    Name,
+    Size,
+    Mtime,
+    Extension,
+    None,
+}
+
+impl From<SortArg> for SortKey {
+    fn from(value: SortArg) -> SortKey {
+        match value {
+            SortArg::Name => SortKey::Name,
+            SortArg::Size => SortKey::Size,
+            SortArg::Mtime => SortKey::Mtime,
+            SortArg::Extension => SortKey::Extension,
+            SortArg::None => SortKey::None,
+        }
+    }
 }
 
 impl TryFrom<&Args> for Config {
@@ -135,11 +262,48 @@ impl TryFrom<&Args> for Config {
             .map_err(|e| format!("`include_pattern` is not valid: {e}"))?
             .map(|glob| glob.compile_matcher());
 
+        let aggregate_threshold = value
+            .min_size
+            .as_deref()
+            .map(size::parse_threshold)
+            .transpose()?
+            .filter(|&threshold| threshold > 0);
+
+        let exclude_globs = value
+            .exclude
+            .iter()
+            .map(|pattern| Glob::new(pattern).map(|glob| glob.compile_matcher()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("`exclude` pattern is not valid: {e}"))?;
+
         Ok(Config {
             use_color: value.color_on || !value.color_off,
             show_hidden: value.show_all,
             max_level: value.max_level,
             include_glob,
+            show_size: value.usage || value.show_size || value.min_size.is_some(),
+            use_disk_usage: value.usage,
+            use_bytes: value.bytes,
+            aggregate_threshold,
+            theme: Theme::from_env(),
+            threads: value.threads.max(1),
+            sort_key: value.sort.into(),
+            sort_case_sensitive: value.sort_case,
+            reverse: value.reverse,
+            dirs_first: value.dirs_first,
+            exclude_globs,
+            use_gitignore: value.gitignore,
+            show_icons: match value.icons {
+                IconsArg::Always => true,
+                IconsArg::Never => false,
+                IconsArg::Auto => io::stdout().is_terminal(),
+            },
+            output_mode: value.output.into(),
+            charset: if value.ascii {
+                Charset::ascii()
+            } else {
+                Charset::unicode()
+            },
         })
     }
 }
@@ -180,10 +344,20 @@ impl<'a> TreePrinter<'a> {
             include_glob: self.config.include_glob.clone(),
             max_level: self.config.max_level,
             show_hidden: self.config.show_hidden,
+            use_disk_usage: self.config.use_disk_usage,
+            aggregate_threshold: self.config.aggregate_threshold,
+            threads: self.config.threads,
+            sort_key: self.config.sort_key,
+            sort_case_sensitive: self.config.sort_case_sensitive,
+            reverse: self.config.reverse,
+            dirs_first: self.config.dirs_first,
+            exclude_globs: self.config.exclude_globs.clone(),
+            use_gitignore: self.config.use_gitignore,
         };
 
         let list = pathiterator::FileIterator::new(path, config);
         let mut list = filter::FilteredIterator::new(list);
+
         if self.config.include_glob.is_none() {
             list.skip_filter();
         }
@@ -192,6 +366,14 @@ impl<'a> TreePrinter<'a> {
     }
 
     fn iterate_folders(&mut self, path: &Path) -> io::Result<DirEntrySummary> {
+        match self.config.output_mode {
+            OutputMode::Tree => self.print_tree(path),
+            OutputMode::Json => self.print_json(path),
+            OutputMode::Paths => self.print_paths(path),
+        }
+    }
+
+    fn print_tree(&mut self, path: &Path) -> io::Result<DirEntrySummary> {
         let mut summary = DirEntrySummary::new();
 
         let mut levels: Vec<bool> = Vec::new();
@@ -199,14 +381,9 @@ impl<'a> TreePrinter<'a> {
 
         for entry in self.get_iterator(path) {
             Self::update_levels(&mut levels, entry.level, entry.is_last);
+            count_entry(&mut summary, &entry);
 
-            if entry.is_dir() {
-                summary.num_folders += 1;
-            } else {
-                summary.num_files += 1;
-            }
-
-            set_line_prefix(&levels, &mut prefix);
+            set_line_prefix(&self.config.charset, &levels, &mut prefix);
             self.print_line(&entry, &prefix)?;
         }
 
@@ -215,10 +392,43 @@ impl<'a> TreePrinter<'a> {
         Ok(summary)
     }
 
+    fn print_json(&mut self, path: &Path) -> io::Result<DirEntrySummary> {
+        let items: Vec<_> = self.get_iterator(path).collect();
+        let summary = summarize(&items);
+
+        let json = output::render_json(
+            &items,
+            summary.num_folders,
+            summary.num_files,
+            self.config.show_size,
+            self.config.use_bytes,
+        );
+        println!("{json}");
+
+        Ok(summary)
+    }
+
+    fn print_paths(&mut self, path: &Path) -> io::Result<DirEntrySummary> {
+        let items: Vec<_> = self.get_iterator(path).collect();
+        let summary = summarize(&items);
+
+        output::write_paths(&items, &mut io::stdout())?;
+
+        Ok(summary)
+    }
+
     fn print_line(&mut self, entry: &pathiterator::IteratorItem, prefix: &str) -> io::Result<()> {
+        if self.config.show_size {
+            let size = size::format_size(entry.size, self.config.use_bytes);
+            print!("[{size:>8}]  ");
+        }
+
         print!("{prefix}");
-        if let Ok(ref metadata) = entry.metadata {
-            print_path(&entry.file_name, metadata, self.term, &self.config)?;
+
+        if entry.aggregated.is_some() {
+            print!("{}", entry.file_name);
+        } else if let Ok(ref metadata) = entry.metadata {
+            print_path(entry, metadata, self.term, &self.config)?;
         } else {
             eprint!("{} [Error]", entry.file_name);
         }
@@ -229,6 +439,9 @@ impl<'a> TreePrinter<'a> {
     }
 }
 
+// One independent CLI flag per bool, mirroring `Config`; see its
+// `#[allow]` for why splitting these up wouldn't help.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Parser)]
 struct Args {
     /// Show hidden files
@@ -249,6 +462,63 @@ struct Args {
     /// Descend only <level> directories deep
     #[clap(short = 'L', long = "level", default_value_t = usize::max_value())]
     max_level: usize,
+    /// Show accumulated size for each entry
+    #[clap(short = 's', long = "size")]
+    show_size: bool,
+    /// Collapse sibling entries smaller than <size> (e.g. `512K`, `1M`,
+    /// `2G`) into a single summary line; implies `--size`
+    #[clap(long = "min-size", value_name = "size")]
+    min_size: Option<String>,
+    /// Show real on-disk usage (`st_blocks * 512`) instead of apparent size;
+    /// implies `--size`
+    #[clap(short = 'u', long = "usage")]
+    usage: bool,
+    /// Print raw byte counts instead of human-readable sizes
+    #[clap(short = 'b', long = "bytes")]
+    bytes: bool,
+    /// Number of worker threads for directory traversal; `1` walks serially
+    #[clap(short = 'j', long = "threads", default_value_t = default_thread_count())]
+    threads: usize,
+    /// How to order sibling entries within each directory
+    #[clap(long = "sort", value_enum, default_value_t = SortArg::Name)]
+    sort: SortArg,
+    /// Reverse the sort order
+    #[clap(short = 'r', long = "reverse")]
+    reverse: bool,
+    /// List directories before files within each directory
+    #[clap(long = "dirs-first")]
+    dirs_first: bool,
+    /// Sort names case-sensitively (default is case-insensitive)
+    #[clap(long = "sort-case")]
+    sort_case: bool,
+    /// Exclude files/directories matching <pattern> (repeatable)
+    #[clap(short = 'I', long = "exclude", value_name = "pattern")]
+    exclude: Vec<String>,
+    /// Hide files ignored by any `.gitignore` met while walking down to them
+    #[clap(long = "gitignore")]
+    gitignore: bool,
+    /// Print a Nerd Font glyph before each entry's name; bare `--icons`
+    /// means `always`
+    #[clap(
+        long = "icons",
+        value_enum,
+        default_value_t = IconsArg::Auto,
+        num_args = 0..=1,
+        default_missing_value = "always"
+    )]
+    icons: IconsArg,
+    /// How to render the walked tree
+    #[clap(long = "output", value_enum, default_value_t = OutputArg::Tree)]
+    output: OutputArg,
+    /// Draw branches with a pure-ASCII charset instead of Unicode
+    /// box-drawing characters
+    #[clap(short = 'A', long = "ascii")]
+    ascii: bool,
+}
+
+fn default_thread_count() -> usize {
+    std::thread::available_parallelism()
+        .map_or(1, std::num::NonZeroUsize::get)
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -256,6 +526,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let config = Config::try_from(&args)?;
 
     let path = Path::new(args.dir.as_str());
+    let output_mode = config.output_mode;
 
     let mut term = get_terminal_printer()?;
     let summary = {
@@ -264,12 +535,25 @@ fn main() -> Result<(), Box<dyn Error>> {
             .map_err(|e| format!("Program failed with error: {e}"))?
     };
 
-    writeln!(
-        &mut term,
-        "\n{} directories, {} files",
-        summary.num_folders, summary.num_files
-    )
-    .map_err(|e| format!("Failed to print summary: {e}"))?;
+    match output_mode {
+        OutputMode::Tree => {
+            writeln!(
+                &mut term,
+                "\n{} directories, {} files",
+                summary.num_folders, summary.num_files
+            )
+            .map_err(|e| format!("Failed to print summary: {e}"))?;
+        }
+        OutputMode::Paths => {
+            eprintln!(
+                "{} directories, {} files",
+                summary.num_folders, summary.num_files
+            );
+        }
+        OutputMode::Json => {
+            // The summary is already embedded in the JSON document.
+        }
+    }
 
     Ok(())
 }
@@ -277,7 +561,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 #[cfg(test)]
 mod tests {
 
-    use std::fs::create_dir_all;
+    use std::fs::{self, create_dir_all};
     use std::process::Command;
 
     const PATH: &'static str = "target/release/tree-rs";
@@ -292,9 +576,33 @@ mod tests {
         stdout_str
     }
 
+    fn run_cmd_env(arg: &[&str], env: &[(&str, &str)]) -> String {
+        let stdout = Command::new(PATH)
+            .args(arg)
+            .envs(env.iter().copied())
+            .output()
+            .expect("command failed")
+            .stdout;
+        let stdout_str = String::from_utf8(stdout).expect("Bad parsing");
+        stdout_str
+    }
+
+    /// Builds the whole `tests/simple` fixture tree from scratch, so tests
+    /// don't depend on files planted by hand outside version control.
+    /// Idempotent: safe to call from every test that needs it.
+    fn setup_fixture() {
+        create_dir_all("tests/simple/yyy/k").unwrap();
+        create_dir_all("tests/simple/yyy/s").unwrap();
+        create_dir_all("tests/simple/yyy/zz/a/b").unwrap();
+        fs::write("tests/simple/yyy/s/a", "").unwrap();
+        fs::write("tests/simple/yyy/s/t", "").unwrap();
+        fs::write("tests/simple/yyy/test.txt", "").unwrap();
+        fs::write("tests/simple/yyy/zz/a/b/c", "").unwrap();
+    }
+
     #[test]
     fn test_normal() {
-        create_dir_all("tests/simple/yyy/k").unwrap();
+        setup_fixture();
         let expected = r#"simple
 └── yyy
     ├── k
@@ -316,7 +624,7 @@ mod tests {
 
     #[test]
     fn test_max_depth() {
-        create_dir_all("tests/simple/yyy/k").unwrap();
+        setup_fixture();
         let expected = r#"simple
 └── yyy
     ├── k
@@ -343,4 +651,154 @@ mod tests {
         let output = run_cmd(&["-n", "-P", "*.txt", "tests/simple"]);
         assert_eq!(expected, output);
     }
+
+    #[test]
+    fn test_output_json() {
+        setup_fixture();
+        let expected = concat!(
+            "{\"summary\":{\"directories\":6,\"files\":4},\"tree\":",
+            "{\"name\":\"simple\",\"type\":\"directory\",\"contents\":[",
+            "{\"name\":\"yyy\",\"type\":\"directory\",\"contents\":[",
+            "{\"name\":\"k\",\"type\":\"directory\",\"contents\":[]},",
+            "{\"name\":\"s\",\"type\":\"directory\",\"contents\":[",
+            "{\"name\":\"a\",\"type\":\"file\"},",
+            "{\"name\":\"t\",\"type\":\"file\"}]},",
+            "{\"name\":\"test.txt\",\"type\":\"file\"},",
+            "{\"name\":\"zz\",\"type\":\"directory\",\"contents\":[",
+            "{\"name\":\"a\",\"type\":\"directory\",\"contents\":[",
+            "{\"name\":\"b\",\"type\":\"directory\",\"contents\":[",
+            "{\"name\":\"c\",\"type\":\"file\"}]}]}]}]}]}}\n",
+        );
+
+        let output = run_cmd(&["--output", "json", "tests/simple"]);
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn test_output_paths() {
+        setup_fixture();
+        let expected = "tests/simple\n\
+             tests/simple/yyy\n\
+             tests/simple/yyy/k\n\
+             tests/simple/yyy/s\n\
+             tests/simple/yyy/s/a\n\
+             tests/simple/yyy/s/t\n\
+             tests/simple/yyy/test.txt\n\
+             tests/simple/yyy/zz\n\
+             tests/simple/yyy/zz/a\n\
+             tests/simple/yyy/zz/a/b\n\
+             tests/simple/yyy/zz/a/b/c\n";
+
+        let output = run_cmd(&["--output", "paths", "tests/simple"]);
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn test_ls_colors_extension() {
+        create_dir_all("tests/colors").unwrap();
+        fs::write("tests/colors/a.txt", "").unwrap();
+
+        let expected = "colors\n`-- \x1b[01;35ma.txt\x1b[0m\n\n0 directories, 1 files\n";
+
+        let output = run_cmd_env(
+            &["-C", "-A", "tests/colors"],
+            &[("LS_COLORS", "*.txt=01;35")],
+        );
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn test_parallel_traversal_matches_serial() {
+        setup_fixture();
+
+        let serial = run_cmd(&["-n", "--threads", "1", "tests/simple"]);
+        let parallel = run_cmd(&["-n", "--threads", "4", "tests/simple"]);
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_sort_by_size() {
+        create_dir_all("tests/sort").unwrap();
+        fs::write("tests/sort/a", "x").unwrap();
+        fs::write("tests/sort/bb", "xyz").unwrap();
+        fs::write("tests/sort/ccc", "xxxxx").unwrap();
+
+        let expected = "sort\n\
+             +-- a\n\
+             +-- bb\n\
+             `-- ccc\n\n\
+             0 directories, 3 files\n";
+
+        let output = run_cmd(&["-n", "-A", "--sort", "size", "tests/sort"]);
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn test_dirs_first() {
+        create_dir_all("tests/dirsfirst/zzz").unwrap();
+        fs::write("tests/dirsfirst/aaa.txt", "").unwrap();
+
+        let expected = "dirsfirst\n+-- zzz\n`-- aaa.txt\n\n1 directories, 1 files\n";
+
+        let output = run_cmd(&["-n", "-A", "--dirs-first", "tests/dirsfirst"]);
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn test_exclude_glob() {
+        create_dir_all("tests/exclude").unwrap();
+        fs::write("tests/exclude/keep.txt", "").unwrap();
+        fs::write("tests/exclude/skip.log", "").unwrap();
+
+        let expected = "exclude\n`-- keep.txt\n\n0 directories, 1 files\n";
+
+        let output = run_cmd(&["-n", "-A", "--exclude", "*.log", "tests/exclude"]);
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn test_gitignore() {
+        create_dir_all("tests/gitignore").unwrap();
+        fs::write("tests/gitignore/.gitignore", "*.log\n").unwrap();
+        fs::write("tests/gitignore/keep.txt", "").unwrap();
+        fs::write("tests/gitignore/skip.log", "").unwrap();
+
+        let expected = "gitignore\n`-- keep.txt\n\n0 directories, 1 files\n";
+
+        let output = run_cmd(&["-n", "-A", "--gitignore", "tests/gitignore"]);
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn test_icons() {
+        create_dir_all("tests/icons").unwrap();
+        fs::write("tests/icons/a.txt", "").unwrap();
+
+        let expected = "\u{f115} icons\n`-- \u{f0f6} a.txt\n\n0 directories, 1 files\n";
+
+        let output = run_cmd(&["-n", "-A", "--icons", "always", "tests/icons"]);
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn test_ascii_charset() {
+        setup_fixture();
+        let expected = r#"simple
+`-- yyy
+    +-- k
+    +-- s
+    |   +-- a
+    |   `-- t
+    +-- test.txt
+    `-- zz
+        `-- a
+            `-- b
+                `-- c
+
+6 directories, 4 files
+"#;
+
+        let output = run_cmd(&["-n", "-A", "tests/simple"]);
+        assert_eq!(expected, output);
+    }
 }
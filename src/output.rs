@@ -0,0 +1,131 @@
+use std::io::{self, Write};
+
+use crate::pathiterator::IteratorItem;
+use crate::size;
+
+/// How the walked tree is rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputMode {
+    /// The classic box-drawing tree (the default).
+    Tree,
+    /// A nested `{"name","type","size"?,"contents":[...]}` document.
+    Json,
+    /// One full path per line, suitable for piping into `xargs`/`fzf`.
+    Paths,
+}
+
+/// Renders `items` (a pre-order walk, root first) as the JSON document
+/// described by `OutputMode::Json`, with the directory/file counts folded
+/// into a top-level `summary` field instead of a trailing text line.
+pub fn render_json(
+    items: &[IteratorItem],
+    num_folders: usize,
+    num_files: usize,
+    show_size: bool,
+    use_bytes: bool,
+) -> String {
+    let mut out = String::new();
+    let mut rest = items;
+
+    out.push_str("{\"summary\":{\"directories\":");
+    out.push_str(&num_folders.to_string());
+    out.push_str(",\"files\":");
+    out.push_str(&num_files.to_string());
+    out.push_str("},\"tree\":");
+
+    if let Some((root, children)) = rest.split_first() {
+        rest = children;
+        write_node(root, &mut rest, show_size, use_bytes, &mut out);
+    } else {
+        out.push_str("null");
+    }
+
+    out.push('}');
+    out
+}
+
+fn write_node(
+    item: &IteratorItem,
+    rest: &mut &[IteratorItem],
+    show_size: bool,
+    use_bytes: bool,
+    out: &mut String,
+) {
+    out.push_str("{\"name\":");
+    push_json_string(out, &item.file_name);
+
+    out.push_str(",\"type\":\"");
+    out.push_str(if item.is_dir() { "directory" } else { "file" });
+    out.push('"');
+
+    if show_size {
+        out.push_str(",\"size\":");
+        out.push_str(&item.size.to_string());
+
+        if !use_bytes {
+            out.push_str(",\"size_human\":");
+            push_json_string(out, &size::human_readable(item.size));
+        }
+    }
+
+    if item.is_dir() {
+        out.push_str(",\"contents\":[");
+
+        let child_level = item.level + 1;
+        let mut first = true;
+
+        while let Some((child, tail)) = rest.split_first() {
+            if child.level < child_level {
+                break;
+            }
+
+            *rest = tail;
+
+            if !first {
+                out.push(',');
+            }
+            first = false;
+
+            write_node(child, rest, show_size, use_bytes, out);
+        }
+
+        out.push(']');
+    }
+
+    out.push('}');
+}
+
+fn push_json_string(out: &mut String, value: &str) {
+    out.push('"');
+
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+}
+
+/// Prints one full path per line, skipping synthetic aggregated entries
+/// which have no real path on disk.
+///
+/// # Errors
+///
+/// Returns an error if writing to `out` fails.
+pub fn write_paths(items: &[IteratorItem], out: &mut impl Write) -> io::Result<()> {
+    for item in items {
+        if item.aggregated.is_some() {
+            continue;
+        }
+
+        writeln!(out, "{}", item.path.display())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,484 @@
+use std::fs;
+use std::fs::Metadata;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use globset::GlobMatcher;
+
+use crate::gitignore::GitignoreStack;
+use crate::size;
+
+// Each field is an independent `--exclude`/`--gitignore`/sort/traversal
+// toggle threaded straight from `main::Config`; there's no natural
+// sub-grouping that would reduce the count rather than just rename it.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone)]
+pub struct FileIteratorConfig {
+    pub include_glob: Option<GlobMatcher>,
+    pub max_level: usize,
+    pub show_hidden: bool,
+    pub use_disk_usage: bool,
+    pub aggregate_threshold: Option<u64>,
+    /// Worker budget for fanning out `read_dir`/`metadata` calls. `1`
+    /// restores fully serial traversal.
+    pub threads: usize,
+    pub sort_key: SortKey,
+    pub sort_case_sensitive: bool,
+    pub reverse: bool,
+    pub dirs_first: bool,
+    /// `--exclude`/`--gitignore` rules. Applied here, before children are
+    /// walked, so an excluded subtree is never stat'd and never inflates
+    /// its parent's bottom-up size; `FilteredIterator` only has to handle
+    /// `--include`, which constrains display rather than membership.
+    pub exclude_globs: Vec<GlobMatcher>,
+    pub use_gitignore: bool,
+}
+
+/// Key used to order siblings within a directory before they're printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    Name,
+    Size,
+    Mtime,
+    Extension,
+    None,
+}
+
+impl SortKey {
+    fn needs_metadata(self) -> bool {
+        matches!(self, SortKey::Size | SortKey::Mtime)
+    }
+}
+
+/// Marks a synthetic entry that stands in for a run of siblings collapsed by
+/// `aggregate_threshold`.
+pub struct AggregateInfo {
+    pub count: usize,
+}
+
+pub struct IteratorItem {
+    pub file_name: String,
+    pub path: PathBuf,
+    pub metadata: io::Result<Metadata>,
+    pub level: usize,
+    pub is_last: bool,
+    /// Accumulated size in bytes: the file's own size, or the sum of a
+    /// directory's children, computed bottom-up.
+    pub size: u64,
+    pub aggregated: Option<AggregateInfo>,
+    /// Whether the entry itself (not its target) is a symlink. `metadata`
+    /// follows symlinks, so this is the only way to tell.
+    pub is_symlink: bool,
+}
+
+impl IteratorItem {
+    pub fn is_dir(&self) -> bool {
+        matches!(&self.metadata, Ok(metadata) if metadata.is_dir())
+    }
+}
+
+struct Node {
+    item: IteratorItem,
+    children: Vec<Node>,
+}
+
+pub struct FileIterator {
+    config: FileIteratorConfig,
+    items: std::vec::IntoIter<IteratorItem>,
+}
+
+impl FileIterator {
+    pub fn new(path: &Path, config: FileIteratorConfig) -> FileIterator {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+
+        // One permit is already "spent" on the calling thread, so the
+        // budget governs the *extra* worker threads fanned out below it.
+        let budget = AtomicUsize::new(config.threads.saturating_sub(1));
+        let is_symlink = fs::symlink_metadata(path)
+            .map_or(false, |metadata| metadata.file_type().is_symlink());
+        let root = build_node(
+            path,
+            file_name,
+            0,
+            true,
+            is_symlink,
+            &config,
+            &budget,
+            None,
+            &GitignoreStack::default(),
+        );
+
+        let mut items = Vec::new();
+        flatten(root, &mut items);
+
+        FileIterator {
+            config,
+            items: items.into_iter(),
+        }
+    }
+
+    pub(crate) fn include_glob(&self) -> Option<&GlobMatcher> {
+        self.config.include_glob.as_ref()
+    }
+}
+
+impl Iterator for FileIterator {
+    type Item = IteratorItem;
+
+    fn next(&mut self) -> Option<IteratorItem> {
+        self.items.next()
+    }
+}
+
+fn flatten(node: Node, out: &mut Vec<IteratorItem>) {
+    out.push(node.item);
+    for child in node.children {
+        flatten(child, out);
+    }
+}
+
+// Every parameter is either identity (`path`/`file_name`/`level`/`is_last`)
+// or state threaded unchanged through the whole recursive descent
+// (`config`/`budget`/`gitignore`); bundling them would just add an
+// indirection without reducing what each call site has to supply.
+#[allow(clippy::too_many_arguments)]
+fn build_node(
+    path: &Path,
+    file_name: String,
+    level: usize,
+    is_last: bool,
+    is_symlink: bool,
+    config: &FileIteratorConfig,
+    budget: &AtomicUsize,
+    metadata: Option<Metadata>,
+    gitignore: &GitignoreStack,
+) -> Node {
+    // Reuse the metadata `sort_listings` already fetched for `--sort
+    // size|mtime` instead of stat'ing the same path twice.
+    let metadata: io::Result<Metadata> = match metadata {
+        Some(metadata) => Ok(metadata),
+        None => fs::metadata(path),
+    };
+    let mut children = Vec::new();
+    let mut size = 0;
+
+    match &metadata {
+        Ok(metadata) if metadata.is_dir() => {
+            if level < config.max_level {
+                // The `.gitignore` directly inside this directory applies to
+                // its children, not to this directory itself.
+                let gitignore = if config.use_gitignore {
+                    gitignore.extended(path)
+                } else {
+                    gitignore.clone()
+                };
+                children = read_children(path, level + 1, config, budget, &gitignore);
+                size = children.iter().map(|child| child.item.size).sum();
+            } else {
+                // Truncated for display, but the size column must still
+                // reflect the full subtree.
+                size = total_subtree_size(path, config, gitignore);
+            }
+        }
+        Ok(metadata) => size = size::entry_size(metadata, config.use_disk_usage),
+        Err(_) => {}
+    }
+
+    if let Some(threshold) = config.aggregate_threshold {
+        children = aggregate_small_children(children, threshold);
+    }
+
+    Node {
+        item: IteratorItem {
+            file_name,
+            path: path.to_path_buf(),
+            metadata,
+            level,
+            is_last,
+            size,
+            aggregated: None,
+            is_symlink,
+        },
+        children,
+    }
+}
+
+/// A not-yet-built sibling, carrying just enough to sort the directory
+/// before any `Node`s (and their recursive work) are created.
+struct Listing {
+    entry: fs::DirEntry,
+    file_name: String,
+    metadata: Option<Metadata>,
+    /// Read straight off the directory entry, so this is free even though
+    /// `metadata` (when present) follows symlinks and can't tell us.
+    is_symlink: bool,
+}
+
+impl Listing {
+    fn new(entry: fs::DirEntry) -> Listing {
+        let is_symlink = entry
+            .file_type()
+            .map_or(false, |file_type| file_type.is_symlink());
+
+        Listing {
+            file_name: entry.file_name().to_string_lossy().into_owned(),
+            metadata: None,
+            is_symlink,
+            entry,
+        }
+    }
+
+    fn is_dir(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .map(Metadata::is_dir)
+            .or_else(|| self.entry.file_type().ok().map(|file_type| file_type.is_dir()))
+            .unwrap_or(false)
+    }
+}
+
+fn sort_listings(listings: &mut [Listing], config: &FileIteratorConfig) {
+    if config.sort_key.needs_metadata() {
+        for listing in listings.iter_mut() {
+            // `fs::metadata` (not `DirEntry::metadata`) so this matches what
+            // `build_node` would otherwise fetch itself, and can be reused.
+            listing.metadata = fs::metadata(listing.entry.path()).ok();
+        }
+    }
+
+    match config.sort_key {
+        SortKey::None => {}
+        SortKey::Name => listings.sort_by(|a, b| compare_names(a, b, config.sort_case_sensitive)),
+        SortKey::Extension => {
+            listings.sort_by(|a, b| compare_extensions(a, b, config.sort_case_sensitive));
+        }
+        SortKey::Size => {
+            listings.sort_by_key(|listing| listing.metadata.as_ref().map_or(0, Metadata::len));
+        }
+        SortKey::Mtime => {
+            listings.sort_by_key(|listing| {
+                listing.metadata.as_ref().and_then(|m| m.modified().ok())
+            });
+        }
+    }
+
+    if config.reverse {
+        listings.reverse();
+    }
+
+    if config.dirs_first {
+        listings.sort_by_key(|listing| !listing.is_dir());
+    }
+}
+
+fn compare_names(a: &Listing, b: &Listing, case_sensitive: bool) -> std::cmp::Ordering {
+    if case_sensitive {
+        a.file_name.cmp(&b.file_name)
+    } else {
+        a.file_name.to_lowercase().cmp(&b.file_name.to_lowercase())
+    }
+}
+
+fn compare_extensions(a: &Listing, b: &Listing, case_sensitive: bool) -> std::cmp::Ordering {
+    extension_of(&a.file_name, case_sensitive)
+        .cmp(&extension_of(&b.file_name, case_sensitive))
+        .then_with(|| compare_names(a, b, case_sensitive))
+}
+
+fn extension_of(file_name: &str, case_sensitive: bool) -> String {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+
+    if case_sensitive {
+        extension.to_string()
+    } else {
+        extension.to_lowercase()
+    }
+}
+
+/// Tries to reserve one worker-thread permit from the shared budget.
+fn try_acquire(budget: &AtomicUsize) -> bool {
+    budget
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |permits| {
+            permits.checked_sub(1)
+        })
+        .is_ok()
+}
+
+/// Whether an entry named `file_name` at `path` should be dropped, under the
+/// `gitignore` stack already extended for the directory it's directly in.
+fn is_excluded(
+    config: &FileIteratorConfig,
+    gitignore: &GitignoreStack,
+    file_name: &str,
+    path: &Path,
+    is_dir: bool,
+) -> bool {
+    if config.exclude_globs.iter().any(|glob| glob.is_match(file_name)) {
+        return true;
+    }
+
+    config.use_gitignore && gitignore.is_ignored(path, is_dir)
+}
+
+fn read_children(
+    dir: &Path,
+    level: usize,
+    config: &FileIteratorConfig,
+    budget: &AtomicUsize,
+    gitignore: &GitignoreStack,
+) -> Vec<Node> {
+    let mut entries: Vec<fs::DirEntry> = fs::read_dir(dir)
+        .map(|read_dir| read_dir.filter_map(Result::ok).collect())
+        .unwrap_or_default();
+
+    entries.retain(|entry| {
+        config.show_hidden || !entry.file_name().to_string_lossy().starts_with('.')
+    });
+
+    let mut listings: Vec<Listing> = entries.into_iter().map(Listing::new).collect();
+    listings.retain(|listing| {
+        !is_excluded(
+            config,
+            gitignore,
+            &listing.file_name,
+            &listing.entry.path(),
+            listing.is_dir(),
+        )
+    });
+    sort_listings(&mut listings, config);
+
+    if listings.is_empty() {
+        return Vec::new();
+    }
+
+    let last = listings.len() - 1;
+    let results: Mutex<Vec<Option<Node>>> = Mutex::new((0..listings.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for (index, listing) in listings.into_iter().enumerate() {
+            let is_last = index == last;
+            let Listing {
+                entry,
+                file_name,
+                metadata,
+                is_symlink,
+            } = listing;
+            let child_path = entry.path();
+
+            if try_acquire(budget) {
+                let results = &results;
+                scope.spawn(move || {
+                    let node = build_node(
+                        &child_path, file_name, level, is_last, is_symlink, config, budget, metadata,
+                        gitignore,
+                    );
+                    results.lock().expect("mutex poisoned")[index] = Some(node);
+                    budget.fetch_add(1, Ordering::SeqCst);
+                });
+            } else {
+                let node = build_node(
+                    &child_path, file_name, level, is_last, is_symlink, config, budget, metadata,
+                    gitignore,
+                );
+                results.lock().expect("mutex poisoned")[index] = Some(node);
+            }
+        }
+    });
+
+    results
+        .into_inner()
+        .expect("mutex poisoned")
+        .into_iter()
+        .map(|node| node.expect("every slot is filled before the scope exits"))
+        .collect()
+}
+
+/// Walks a subtree purely to total its size, ignoring `max_level` so that a
+/// directory truncated from display still reports its real size. Honors the
+/// same `show_hidden`/`exclude_globs`/`gitignore` rules as `read_children`,
+/// so a truncated directory's size matches what it would have summed to had
+/// it been walked normally, except for symlinks: they are not followed here
+/// (the link's own size is counted), whereas the normal walk follows them.
+fn total_subtree_size(path: &Path, config: &FileIteratorConfig, gitignore: &GitignoreStack) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+
+    if metadata.is_dir() {
+        let gitignore = if config.use_gitignore {
+            gitignore.extended(path)
+        } else {
+            gitignore.clone()
+        };
+
+        fs::read_dir(path)
+            .map(|read_dir| {
+                read_dir
+                    .filter_map(Result::ok)
+                    .filter(|entry| {
+                        let file_name = entry.file_name().to_string_lossy().into_owned();
+
+                        if !config.show_hidden && file_name.starts_with('.') {
+                            return false;
+                        }
+
+                        let is_dir = entry
+                            .file_type()
+                            .map_or(false, |file_type| file_type.is_dir());
+                        !is_excluded(config, &gitignore, &file_name, &entry.path(), is_dir)
+                    })
+                    .map(|entry| total_subtree_size(&entry.path(), config, &gitignore))
+                    .sum()
+            })
+            .unwrap_or(0)
+    } else {
+        size::entry_size(&metadata, config.use_disk_usage)
+    }
+}
+
+/// Collapses sibling entries smaller than `threshold` into a single synthetic
+/// node, modeled on `dutree`'s aggregation.
+fn aggregate_small_children(children: Vec<Node>, threshold: u64) -> Vec<Node> {
+    let (small, mut large): (Vec<Node>, Vec<Node>) =
+        children.into_iter().partition(|node| node.item.size < threshold);
+
+    if !small.is_empty() {
+        let total: u64 = small.iter().map(|node| node.item.size).sum();
+        let level = large
+            .first()
+            .or(small.first())
+            .map_or(0, |node| node.item.level);
+        let word = if small.len() == 1 { "file" } else { "files" };
+
+        large.push(Node {
+            item: IteratorItem {
+                file_name: format!("<{} {word}, {}>", small.len(), size::human_readable(total)),
+                path: PathBuf::new(),
+                metadata: Err(io::Error::other("aggregated entry")),
+                level,
+                is_last: false,
+                size: total,
+                aggregated: Some(AggregateInfo { count: small.len() }),
+                is_symlink: false,
+            },
+            children: Vec::new(),
+        });
+    }
+
+    if let Some(last) = large.len().checked_sub(1) {
+        for node in &mut large[..last] {
+            node.item.is_last = false;
+        }
+        large[last].item.is_last = true;
+    }
+
+    large
+}
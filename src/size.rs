@@ -0,0 +1,93 @@
+use std::fs::Metadata;
+
+/// Bytes per on-disk block, matching the kernel's `st_blocks` unit.
+const BLOCK_SIZE: u64 = 512;
+
+/// Returns the apparent size of a file in bytes, or its real on-disk usage
+/// (`st_blocks * 512`) when `use_disk_usage` is set. On-disk usage is only
+/// read on Linux; on other platforms the apparent size is returned either way.
+pub fn entry_size(metadata: &Metadata, use_disk_usage: bool) -> u64 {
+    if use_disk_usage {
+        disk_usage(metadata)
+    } else {
+        metadata.len()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn disk_usage(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.blocks() * BLOCK_SIZE
+}
+
+#[cfg(not(target_os = "linux"))]
+fn disk_usage(metadata: &Metadata) -> u64 {
+    metadata.len()
+}
+
+/// Formats a byte count in binary units: one decimal place and a
+/// `K`/`M`/`G`/`T` suffix, or the whole byte count with a `B` suffix below
+/// 1024 bytes.
+pub fn human_readable(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+
+    // Display only: precision beyond 2^52 bytes (4 PB) is not observable
+    // in the rendered string, so the narrowing is harmless.
+    #[allow(clippy::cast_precision_loss)]
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes}{}", UNITS[0])
+    } else {
+        format!("{size:.1}{}", UNITS[unit])
+    }
+}
+
+/// Formats a byte count either as a raw number or human-readable, depending
+/// on `--bytes`.
+pub fn format_size(bytes: u64, use_bytes: bool) -> String {
+    if use_bytes {
+        bytes.to_string()
+    } else {
+        human_readable(bytes)
+    }
+}
+
+/// Parses a size threshold like `512K`, `1M`, `2G` (case-insensitive, suffix
+/// optional, meaning raw bytes) into a byte count.
+///
+/// # Errors
+///
+/// Returns an error if `input` isn't a valid number, or its unit suffix
+/// isn't one of `B`/`K`/`M`/`G`/`T`.
+pub fn parse_threshold(input: &str) -> Result<u64, String> {
+    let trimmed = input.trim();
+    let split_at = trimmed
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(split_at);
+
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("`{input}` is not a valid size"))?;
+
+    let multiplier = match unit.to_ascii_uppercase().as_str() {
+        "" | "B" => 1.0,
+        "K" => 1024.0,
+        "M" => 1024.0 * 1024.0,
+        "G" => 1024.0 * 1024.0 * 1024.0,
+        "T" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        other => return Err(format!("unknown size unit `{other}` in `{input}`")),
+    };
+
+    // `value` is rejected above unless it parsed from a plain, non-negative
+    // number, so the conversion never actually truncates a sign.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok((value * multiplier) as u64)
+}
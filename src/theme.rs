@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs::Metadata;
+
+use crate::is_executable;
+use crate::pathiterator::IteratorItem;
+
+/// SGR codes used when `LS_COLORS` is unset, matching this tool's
+/// historical dir/executable-only scheme.
+const DEFAULT_DIR: &str = "01;34";
+const DEFAULT_EXEC: &str = "01;32";
+
+/// A lookup table built from `LS_COLORS`, resolving the SGR sequence to
+/// paint an entry by file type first, then longest-matching extension.
+pub struct Theme {
+    codes: HashMap<String, String>,
+    extensions: HashMap<String, String>,
+}
+
+impl Theme {
+    /// Parses `LS_COLORS`, falling back to this tool's historical defaults
+    /// when the variable is unset.
+    pub fn from_env() -> Theme {
+        env::var("LS_COLORS")
+            .map(|value| Theme::parse(&value))
+            .unwrap_or_else(|_| Theme::defaults())
+    }
+
+    fn defaults() -> Theme {
+        let mut codes = HashMap::new();
+        codes.insert("di".to_string(), DEFAULT_DIR.to_string());
+        codes.insert("ex".to_string(), DEFAULT_EXEC.to_string());
+
+        Theme {
+            codes,
+            extensions: HashMap::new(),
+        }
+    }
+
+    fn parse(value: &str) -> Theme {
+        let mut codes = HashMap::new();
+        let mut extensions = HashMap::new();
+
+        for entry in value.split(':').filter(|entry| !entry.is_empty()) {
+            let Some((key, sgr)) = entry.split_once('=') else {
+                continue;
+            };
+
+            if let Some(ext) = key.strip_prefix("*.") {
+                extensions.insert(ext.to_ascii_lowercase(), sgr.to_string());
+            } else {
+                codes.insert(key.to_string(), sgr.to_string());
+            }
+        }
+
+        Theme { codes, extensions }
+    }
+
+    /// Resolves the SGR sequence for an entry, or `None` to print unstyled.
+    pub fn resolve(&self, item: &IteratorItem, metadata: &Metadata) -> Option<&str> {
+        if item.is_symlink {
+            if let Some(sgr) = self.codes.get("ln") {
+                return Some(sgr.as_str());
+            }
+        }
+
+        if metadata.is_dir() {
+            return self.codes.get("di").map(String::as_str);
+        }
+
+        if let Some(code) = special_file_code(metadata) {
+            if let Some(sgr) = self.codes.get(code) {
+                return Some(sgr.as_str());
+            }
+        }
+
+        if let Some(sgr) = self.extension_match(&item.file_name) {
+            return Some(sgr);
+        }
+
+        if is_executable(metadata) {
+            return self.codes.get("ex").map(String::as_str);
+        }
+
+        self.codes.get("fi").map(String::as_str)
+    }
+
+    /// Tries successive suffixes after each `.`, longest first, so
+    /// `*.tar.gz` beats a plain `*.gz` entry.
+    fn extension_match(&self, file_name: &str) -> Option<&str> {
+        let lower = file_name.to_ascii_lowercase();
+        let mut rest = lower.as_str();
+
+        while let Some(dot) = rest.find('.') {
+            rest = &rest[dot + 1..];
+            if let Some(sgr) = self.extensions.get(rest) {
+                return Some(sgr.as_str());
+            }
+        }
+
+        None
+    }
+}
+
+/// LS_COLORS key for a FIFO/socket/block-device/char-device entry, or
+/// `None` for anything else (regular files, which fall through to
+/// extension/`ex`/`fi` matching).
+#[cfg(not(target_os = "linux"))]
+fn special_file_code(_metadata: &Metadata) -> Option<&'static str> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn special_file_code(metadata: &Metadata) -> Option<&'static str> {
+    use std::os::unix::fs::FileTypeExt;
+    let file_type = metadata.file_type();
+
+    if file_type.is_fifo() {
+        Some("pi")
+    } else if file_type.is_socket() {
+        Some("so")
+    } else if file_type.is_block_device() {
+        Some("bd")
+    } else if file_type.is_char_device() {
+        Some("cd")
+    } else {
+        None
+    }
+}